@@ -12,9 +12,12 @@ use aphrodite::{
     CacheSize, HashSet, LoaderArgs, Repo, AphroditeEngine,
 };
 use safetensors::Dtype;
-use std::{path::PathBuf, rc::Rc, sync::Arc};
+use std::{io::Read, path::PathBuf, rc::Rc, sync::Arc};
 use tch::{nn::VarStore, Device, Kind, Tensor};
 
+/// GGUF container magic, `"GGUF"` read as a little-endian `u32`.
+const GGUF_MAGIC: u32 = 0x4655_4747;
+
 use super::{
     config::{CommonModelConfig, ModelConfig, AphroditeModelConfig},
     tmodel::{TModelInner, TchLoaderArgs},
@@ -46,10 +49,440 @@ fn read_tensor(s: &safetensors::SafeTensors, name: &str) -> Result<Tensor> {
     Ok(tensor)
 }
 
+/// Minimal GGUF reader.
+///
+/// GGUF stores a header (`magic`, `version`, `tensor_count`, `metadata_kv_count`),
+/// a run of typed metadata key/value pairs, then one info record per tensor.  The
+/// tensor data itself begins at the file offset rounded up to `general.alignment`
+/// (32 by default); each tensor's `offset` is relative to that section.
+mod gguf {
+    use anyhow::{bail, Result};
+    use std::collections::HashMap;
+
+    // GGML tensor type tags we know how to dequantize.
+    pub const GGML_F32: u32 = 0;
+    pub const GGML_F16: u32 = 1;
+    pub const GGML_Q4_0: u32 = 2;
+    pub const GGML_Q8_0: u32 = 8;
+
+    // GGUF metadata value-type tags.
+    const VT_U8: u32 = 0;
+    const VT_I8: u32 = 1;
+    const VT_U16: u32 = 2;
+    const VT_I16: u32 = 3;
+    const VT_U32: u32 = 4;
+    const VT_I32: u32 = 5;
+    const VT_F32: u32 = 6;
+    const VT_BOOL: u32 = 7;
+    const VT_STRING: u32 = 8;
+    const VT_ARRAY: u32 = 9;
+    const VT_U64: u32 = 10;
+    const VT_I64: u32 = 11;
+    const VT_F64: u32 = 12;
+
+    /// A decoded metadata value.  Integers are widened to `i64`, floats to `f64`;
+    /// arrays keep their decoded elements so callers can pull out e.g. scalar counts.
+    #[derive(Debug, Clone)]
+    pub enum Value {
+        Int(i64),
+        Float(f64),
+        Bool(bool),
+        Str(String),
+        Array(Vec<Value>),
+    }
+
+    impl Value {
+        pub fn as_i64(&self) -> Option<i64> {
+            match self {
+                Value::Int(v) => Some(*v),
+                Value::Float(v) => Some(*v as i64),
+                Value::Bool(v) => Some(*v as i64),
+                _ => None,
+            }
+        }
+
+        pub fn as_f64(&self) -> Option<f64> {
+            match self {
+                Value::Float(v) => Some(*v),
+                Value::Int(v) => Some(*v as f64),
+                _ => None,
+            }
+        }
+
+        pub fn as_str(&self) -> Option<&str> {
+            match self {
+                Value::Str(v) => Some(v),
+                _ => None,
+            }
+        }
+    }
+
+    /// One tensor info record: its name, shape and storage type, plus the byte
+    /// offset of its data relative to the tensor-data section.
+    #[derive(Debug, Clone)]
+    pub struct TensorInfo {
+        pub name: String,
+        pub dims: Vec<i64>,
+        pub ggml_type: u32,
+        pub offset: u64,
+    }
+
+    /// Parsed GGUF header: metadata and tensor directory, plus the absolute file
+    /// offset at which the tensor-data section begins.
+    pub struct Header {
+        pub metadata: HashMap<String, Value>,
+        pub tensors: Vec<TensorInfo>,
+        pub data_offset: usize,
+    }
+
+    struct Cursor<'a> {
+        buf: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> Cursor<'a> {
+        fn new(buf: &'a [u8]) -> Self {
+            Cursor { buf, pos: 0 }
+        }
+
+        fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+            if self.pos + n > self.buf.len() {
+                bail!("unexpected end of gguf header");
+            }
+            let s = &self.buf[self.pos..self.pos + n];
+            self.pos += n;
+            Ok(s)
+        }
+
+        fn u32(&mut self) -> Result<u32> {
+            Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+        }
+
+        fn u64(&mut self) -> Result<u64> {
+            Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+        }
+
+        fn string(&mut self) -> Result<String> {
+            let len = self.u64()? as usize;
+            let bytes = self.take(len)?;
+            Ok(String::from_utf8_lossy(bytes).into_owned())
+        }
+
+        fn scalar(&mut self, value_type: u32) -> Result<Value> {
+            let v = match value_type {
+                VT_U8 => Value::Int(self.take(1)?[0] as i64),
+                VT_I8 => Value::Int(self.take(1)?[0] as i8 as i64),
+                VT_U16 => Value::Int(u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as i64),
+                VT_I16 => Value::Int(i16::from_le_bytes(self.take(2)?.try_into().unwrap()) as i64),
+                VT_U32 => Value::Int(self.u32()? as i64),
+                VT_I32 => Value::Int(self.u32()? as i32 as i64),
+                VT_U64 => Value::Int(self.u64()? as i64),
+                VT_I64 => Value::Int(self.u64()? as i64),
+                VT_F32 => Value::Float(f32::from_le_bytes(self.take(4)?.try_into().unwrap()) as f64),
+                VT_F64 => Value::Float(f64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+                VT_BOOL => Value::Bool(self.take(1)?[0] != 0),
+                VT_STRING => Value::Str(self.string()?),
+                t => bail!("unsupported gguf value type {t}"),
+            };
+            Ok(v)
+        }
+
+        fn value(&mut self) -> Result<Value> {
+            let value_type = self.u32()?;
+            if value_type == VT_ARRAY {
+                let inner = self.u32()?;
+                let count = self.u64()? as usize;
+                let mut items = Vec::with_capacity(count);
+                for _ in 0..count {
+                    items.push(self.scalar(inner)?);
+                }
+                Ok(Value::Array(items))
+            } else {
+                self.scalar(value_type)
+            }
+        }
+    }
+
+    /// Parse the GGUF header out of a memory-mapped file.
+    pub fn parse(content: &[u8]) -> Result<Header> {
+        let mut c = Cursor::new(content);
+        let magic = c.u32()?;
+        if magic != super::GGUF_MAGIC {
+            bail!("not a gguf file: bad magic {magic:#x}");
+        }
+        let _version = c.u32()?;
+        let tensor_count = c.u64()? as usize;
+        let metadata_kv_count = c.u64()? as usize;
+
+        let mut metadata = HashMap::with_capacity(metadata_kv_count);
+        for _ in 0..metadata_kv_count {
+            let key = c.string()?;
+            let value = c.value()?;
+            metadata.insert(key, value);
+        }
+
+        let mut tensors = Vec::with_capacity(tensor_count);
+        for _ in 0..tensor_count {
+            let name = c.string()?;
+            let n_dims = c.u32()? as usize;
+            let mut dims = Vec::with_capacity(n_dims);
+            for _ in 0..n_dims {
+                dims.push(c.u64()? as i64);
+            }
+            // GGUF lists dimensions fastest-varying first; torch expects the
+            // outermost dimension first, so reverse to row-major order.
+            dims.reverse();
+            let ggml_type = c.u32()?;
+            let offset = c.u64()?;
+            tensors.push(TensorInfo {
+                name,
+                dims,
+                ggml_type,
+                offset,
+            });
+        }
+
+        let alignment = metadata
+            .get("general.alignment")
+            .and_then(Value::as_i64)
+            .unwrap_or(32) as usize;
+        let data_offset = c.pos.div_ceil(alignment) * alignment;
+
+        Ok(Header {
+            metadata,
+            tensors,
+            data_offset,
+        })
+    }
+}
+
+/// Decode a half-precision value stored as a little-endian `u16` into `f32`.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = (bits >> 15) & 1;
+    let exp = (bits >> 10) & 0x1f;
+    let mant = bits & 0x3ff;
+    let val = if exp == 0 {
+        // Subnormal.
+        (mant as f32) * 2f32.powi(-24)
+    } else if exp == 0x1f {
+        if mant == 0 {
+            f32::INFINITY
+        } else {
+            f32::NAN
+        }
+    } else {
+        (1.0 + mant as f32 / 1024.0) * 2f32.powi(exp as i32 - 15)
+    };
+    if sign == 1 {
+        -val
+    } else {
+        val
+    }
+}
+
+/// Dequantize one GGUF tensor into a contiguous `f32` buffer, then build a
+/// `Tensor` of the requested shape.  Q8_0/Q4_0 are expanded block-by-block the
+/// same way `llama.cpp` lays them out; F16/F32 pass straight through.
+fn read_gguf_tensor(data: &[u8], info: &gguf::TensorInfo) -> Result<Tensor> {
+    let numel: i64 = info.dims.iter().product();
+    let numel = numel as usize;
+    let base = info.offset as usize;
+    let mut out = vec![0f32; numel];
+
+    // Bounds-checked slice into the tensor-data section: a truncated or corrupt
+    // GGUF returns an error the rest of the module can propagate rather than
+    // panicking on an out-of-range index.
+    let at = |start: usize, len: usize| -> Result<&[u8]> {
+        data.get(start..start + len)
+            .ok_or_else(|| anyhow::anyhow!("gguf tensor {} truncated", info.name))
+    };
+    let f16_at = |start: usize| -> Result<f32> {
+        Ok(f16_to_f32(u16::from_le_bytes(at(start, 2)?.try_into().unwrap())))
+    };
+
+    match info.ggml_type {
+        gguf::GGML_F32 => {
+            let src = at(base, numel * 4)?;
+            for (i, o) in out.iter_mut().enumerate() {
+                *o = f32::from_le_bytes(src[i * 4..i * 4 + 4].try_into().unwrap());
+            }
+        }
+        gguf::GGML_F16 => {
+            let src = at(base, numel * 2)?;
+            for (i, o) in out.iter_mut().enumerate() {
+                *o = f16_to_f32(u16::from_le_bytes(src[i * 2..i * 2 + 2].try_into().unwrap()));
+            }
+        }
+        gguf::GGML_Q8_0 => {
+            // Block: f16 scale + 32 x i8.
+            const QK: usize = 32;
+            let block = 2 + QK;
+            for (b, chunk) in out.chunks_mut(QK).enumerate() {
+                let off = base + b * block;
+                let scale = f16_at(off)?;
+                let src = at(off + 2, chunk.len())?;
+                for (i, o) in chunk.iter_mut().enumerate() {
+                    *o = src[i] as i8 as f32 * scale;
+                }
+            }
+        }
+        gguf::GGML_Q4_0 => {
+            // Block: f16 scale + 16 bytes holding 32 nibbles centered at 8.
+            const QK: usize = 32;
+            let block = 2 + QK / 2;
+            for (b, chunk) in out.chunks_mut(QK).enumerate() {
+                let off = base + b * block;
+                let scale = f16_at(off)?;
+                let src = at(off + 2, QK / 2)?;
+                for i in 0..QK / 2 {
+                    let byte = src[i];
+                    chunk[i] = ((byte & 0x0f) as i32 - 8) as f32 * scale;
+                    chunk[i + QK / 2] = ((byte >> 4) as i32 - 8) as f32 * scale;
+                }
+            }
+        }
+        t => bail!("unsupported ggml tensor type {t}"),
+    }
+
+    let tensor = Tensor::from_slice(&out).reshape(&info.dims);
+    Ok(tensor)
+}
+
+/// Translate a GGUF tensor name into the VarStore key the model constructors
+/// use.  GGUF ships llama weights as `blk.N.attn_q.weight`, `token_embd.weight`,
+/// `output.weight`, … whereas the model is built with the HF names
+/// (`model.layers.N.self_attn.q_proj.weight`, …); without this the lookup in
+/// [`load_gguf_model`] misses every tensor.  Returns `None` for names we don't
+/// map (they fall through to the usual not-found handling).
+fn gguf_name_to_var(name: &str) -> Option<String> {
+    // Top-level, non-layer tensors.
+    match name {
+        "token_embd.weight" => return Some("model.embed_tokens.weight".to_string()),
+        "output_norm.weight" => return Some("model.norm.weight".to_string()),
+        "output.weight" => return Some("lm_head.weight".to_string()),
+        _ => {}
+    }
+
+    // Per-layer tensors: `blk.N.<part>`.
+    let rest = name.strip_prefix("blk.")?;
+    let (idx, part) = rest.split_once('.')?;
+    let _: usize = idx.parse().ok()?;
+    let mapped = match part {
+        "attn_q.weight" => "self_attn.q_proj.weight",
+        "attn_k.weight" => "self_attn.k_proj.weight",
+        "attn_v.weight" => "self_attn.v_proj.weight",
+        "attn_output.weight" => "self_attn.o_proj.weight",
+        "attn_norm.weight" => "input_layernorm.weight",
+        "ffn_norm.weight" => "post_attention_layernorm.weight",
+        "ffn_gate.weight" => "mlp.gate_proj.weight",
+        "ffn_up.weight" => "mlp.up_proj.weight",
+        "ffn_down.weight" => "mlp.down_proj.weight",
+        _ => return None,
+    };
+    Some(format!("model.layers.{idx}.{mapped}"))
+}
+
+fn load_gguf_model(
+    aphrodite_config: &AphroditeConfig<TModel>,
+    filename: &PathBuf,
+) -> Result<Box<dyn TModelInner>> {
+    let mut vs = VarStore::new(aphrodite_config.model.device.clone());
+
+    let rc_cfg = Rc::new(aphrodite_config.model.clone());
+    let mut model: Box<dyn TModelInner> = match aphrodite_config.model.model_type {
+        ModelType::Llama => Box::new(llama::Llama::load(vs.root(), &rc_cfg).unwrap()),
+        ModelType::Phi => Box::new(phi::MixFormerSequentialForCausalLM::new(&rc_cfg, vs.root())),
+    };
+
+    vs.set_kind(aphrodite_config.model.dtype);
+
+    let fp = std::fs::File::open(filename)?;
+    let content = unsafe { memmap2::MmapOptions::new().map(&fp)? };
+    let header = gguf::parse(&content)?;
+    let data = content
+        .get(header.data_offset..)
+        .ok_or_else(|| anyhow::anyhow!("gguf tensor-data section past end of file"))?;
+
+    let mut vars = vs.variables();
+
+    let bar = indicatif::ProgressBar::new(header.tensors.len() as u64);
+    bar.set_style(
+        indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:60.cyan/blue} {pos:>4}/{len:4} [{eta_precise}] {msg}",
+        )
+        .unwrap(),
+    );
+
+    for info in &header.tensors {
+        let target_name = gguf_name_to_var(&info.name).unwrap_or_else(|| info.name.to_string());
+        if !vars.contains_key(&target_name) {
+            if info.name.ends_with(".inv_freq") {
+                // OK
+            } else {
+                log::warn!("variable {} ({}) not found in the model", target_name, info.name);
+            }
+            continue;
+        }
+
+        let src_tensor = read_gguf_tensor(data, info)?;
+        let mut var = vars.remove(&target_name).unwrap();
+        assert!(var.size() == src_tensor.size());
+        var.f_copy_(&src_tensor)?;
+
+        bar.inc(1);
+        if bar.is_hidden() {
+            eprint!(".");
+        }
+    }
+
+    // Tied embeddings: llama GGUF checkpoints that share the input and output
+    // projections ship only `token_embd.weight` (no `output.weight`), so the
+    // model's `lm_head.weight` is still unfilled.  Copy the embedding tensor
+    // into it rather than aborting on the leftover var.
+    if let Some(mut lm_head) = vars.remove("lm_head.weight") {
+        let embd = header
+            .tensors
+            .iter()
+            .find(|t| t.name == "token_embd.weight")
+            .ok_or_else(|| anyhow::anyhow!("lm_head.weight unset and no token_embd to tie it to"))?;
+        let src_tensor = read_gguf_tensor(data, embd)?;
+        assert!(lm_head.size() == src_tensor.size());
+        lm_head.f_copy_(&src_tensor)?;
+    }
+
+    if vars.len() > 0 {
+        bail!("{} variables not found in the model: {vars:?}", vars.len());
+    }
+
+    if bar.is_hidden() {
+        eprintln!(" done");
+    }
+    bar.finish();
+
+    log::info!("model loaded");
+
+    model.finalize();
+
+    Ok(model)
+}
+
 fn load_model(
     aphrodite_config: &AphroditeConfig<TModel>,
     filenames: Vec<PathBuf>,
+    model_args: &TchLoaderArgs,
 ) -> Result<Box<dyn TModelInner>> {
+    // Quantize-on-load requires the llama/phi constructors to declare each linear
+    // weight at the packed int8/int4 shape with a sibling `.weight_scale`, and
+    // the forward to dequantize (or run an int8 matmul) at compute time.  None of
+    // that is wired, so refuse `--quant` up front rather than copying weights at
+    // full precision and pretending the footprint shrank.
+    if model_args.quant.is_some() {
+        bail!(
+            "quantize-on-load is not supported: the llama/phi quant-aware \
+             constructors and forward dequantization are required first"
+        );
+    }
+
     let mut vs = VarStore::new(aphrodite_config.model.device.clone());
 
     let rc_cfg = Rc::new(aphrodite_config.model.clone());
@@ -88,6 +521,7 @@ fn load_model(
 
             // Using from_blob here instead of from_data_size avoids some unnecessary copy.
             let src_tensor = read_tensor(&safetensors, vname)?;
+
             let mut var = vars.remove(&target_name).unwrap();
             assert!(var.size() == src_tensor.size());
             // println!("copying to {var:?} from {src_tensor:?}");
@@ -116,7 +550,30 @@ fn load_model(
     Ok(model)
 }
 
+/// Return the first four bytes of `file` as a little-endian `u32`, if readable.
+fn peek_magic(file: &PathBuf) -> Option<u32> {
+    let mut f = std::fs::File::open(file).ok()?;
+    let mut buf = [0u8; 4];
+    f.read_exact(&mut buf).ok()?;
+    Some(u32::from_le_bytes(buf))
+}
+
+/// Locate the repo's GGUF weight file.  Only a file named `model.gguf` is
+/// looked up; `*.gguf` files under other names are not found.  Returns the
+/// fetched path only when its header carries the GGUF magic.
+fn gguf_filename(repo: &Repo) -> Option<PathBuf> {
+    let path = repo.get("model.gguf").ok()?;
+    match peek_magic(&path) {
+        Some(GGUF_MAGIC) => Some(path),
+        _ => None,
+    }
+}
+
 fn model_filenames(repo: &Repo) -> Result<Vec<PathBuf>> {
+    if let Some(gguf) = gguf_filename(repo) {
+        return Ok(vec![gguf]);
+    }
+
     let idx = repo.read("model.safetensors.index.json");
 
     let filenames = if let Ok(idx) = idx {
@@ -165,7 +622,27 @@ pub(super) fn load_aphrodite_engine(
     reset_mem_stats(device);
     log_mem_stats("initial", device);
 
-    let model = load_model(&aphrodite_config, filenames)?;
+    // Multiple devices requested: tensor-parallel loading splits each weight
+    // across ranks, but correctness also needs the all-reduce (row-parallel
+    // o_proj/down_proj) and gather (column-parallel QKV) collectives in the
+    // `llama`/`phi` forward — without them each rank computes a partial sum that
+    // is never combined, yielding wrong logits.  Those forward changes are not
+    // wired, so refuse the multi-device path rather than loading a model that
+    // would produce incorrect output.
+    if model_args.devices.len() > 1 {
+        bail!(
+            "tensor-parallel loading across {} devices is not supported: the \
+             row/column-parallel all-reduce/gather collectives in the model \
+             forward are required first",
+            model_args.devices.len()
+        );
+    }
+
+    let model = if filenames.len() == 1 && peek_magic(&filenames[0]) == Some(GGUF_MAGIC) {
+        load_gguf_model(&aphrodite_config, &filenames[0])?
+    } else {
+        load_model(&aphrodite_config, filenames, &model_args)?
+    };
 
     log_mem_stats("model fully loaded", device);
 
@@ -210,22 +687,30 @@ fn profile_model(config: Arc<AphroditeConfig<TModel>>, model: &Box<dyn TModelInn
     let max_cpu = 2 << 30; // 2GiB
     let cpu_cache_size = std::cmp::min(max_cpu, gpu_cache_size);
 
+    // Cold tier: the disk tier is disabled.  No disk budget is read from the
+    // config, and `disk_cache_size` is always 0, so `CacheSize::disk` is
+    // always 0 blocks.
+    let disk_cache_size = 0;
+
     let elt_size = CacheEngine::get_cache_block_size(&config);
 
     let r = CacheSize {
         cpu: cpu_cache_size / elt_size,
         gpu: gpu_cache_size / elt_size,
+        disk: disk_cache_size / elt_size,
     };
 
     let token_kv_size = elt_size / config.model.cache.block_size;
 
     const G: f64 = 1024.0 * 1024.0 * 1024.0;
     log::info!(
-        "caches: gpu:{:.3}GiB cpu:{:.3}GiB; blocks: {}/{}; tokens: {}/{}; {}KiB/token",
+        "caches: gpu:{:.3}GiB cpu:{:.3}GiB disk:{:.3}GiB; blocks: {}/{}/{}; tokens: {}/{}; {}KiB/token",
         gpu_cache_size as f64 / G,
         cpu_cache_size as f64 / G,
+        disk_cache_size as f64 / G,
         r.gpu,
         r.cpu,
+        r.disk,
         r.gpu * config.model.cache.block_size,
         r.cpu * config.model.cache.block_size,
         token_kv_size / 1024,
@@ -241,7 +726,15 @@ pub(super) fn load_model_config(
     let repo = Repo::from(args)?;
     log::info!("loading the model from {}", repo);
 
-    let bytes = repo.read("config.json")?;
+    // A GGUF checkpoint carries its hyper-parameters inline, so synthesize the
+    // `config.json` we would otherwise read from the repo out of its metadata.
+    // Either source is run through `coerce_config` so the alias/default path
+    // (e.g. the `rms_norm_eps`/`rope_theta` fallbacks) applies uniformly.
+    let raw = match gguf_config_json(&repo)? {
+        Some(bytes) => bytes,
+        None => repo.read("config.json")?,
+    };
+    let bytes = coerce_config(&raw)?;
     let mut err = String::new();
 
     let cfg = load_one_config::<llama::LlamaConfig>(&mut err, args, model_args, "llama", &bytes)
@@ -258,6 +751,191 @@ pub(super) fn load_model_config(
     }
 }
 
+/// Declared target type, accepted aliases and optional default for one
+/// `config.json` field.  Drives the coercion pass in [`coerce_config`].
+struct FieldSpec {
+    name: &'static str,
+    ty: &'static str,
+    aliases: &'static [&'static str],
+    default: Option<&'static str>,
+}
+
+/// The fields [`coerce_config`] normalizes.  Adding an alias or default for a
+/// new HF config variant is a one-line edit here, with no new config struct.
+const FIELD_SPECS: &[FieldSpec] = &[
+    FieldSpec { name: "hidden_size", ty: "int", aliases: &["n_embd", "d_model"], default: None },
+    FieldSpec { name: "intermediate_size", ty: "int", aliases: &["n_inner", "ffn_dim"], default: None },
+    FieldSpec { name: "num_hidden_layers", ty: "int", aliases: &["n_layer", "num_layers"], default: None },
+    FieldSpec { name: "num_attention_heads", ty: "int", aliases: &["n_head", "num_heads"], default: None },
+    FieldSpec { name: "num_key_value_heads", ty: "int", aliases: &["num_kv_heads"], default: None },
+    FieldSpec { name: "vocab_size", ty: "int", aliases: &[], default: None },
+    FieldSpec { name: "max_position_embeddings", ty: "int", aliases: &["n_positions", "max_seq_len"], default: None },
+    FieldSpec { name: "rms_norm_eps", ty: "float", aliases: &["layer_norm_eps", "layer_norm_epsilon"], default: Some("1e-5") },
+    FieldSpec { name: "rope_theta", ty: "float", aliases: &["rope_base"], default: Some("10000.0") },
+    FieldSpec { name: "tie_word_embeddings", ty: "bool", aliases: &[], default: None },
+    FieldSpec { name: "model_type", ty: "string", aliases: &[], default: None },
+];
+
+/// Named-conversion registry: coerce `v` to the `target` type, tolerating the
+/// string↔number↔bool mismatches real-world HF configs ship.  Returns `None`
+/// when the value cannot be represented as `target`.
+fn coerce_value(target: &str, v: &serde_json::Value) -> Option<serde_json::Value> {
+    use serde_json::Value as V;
+    match target {
+        "int" => match v {
+            V::Number(n) => n.as_i64().map(V::from).or_else(|| n.as_f64().map(|f| V::from(f as i64))),
+            V::String(s) => s
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .map(V::from)
+                .or_else(|| s.trim().parse::<f64>().ok().map(|f| V::from(f as i64))),
+            V::Bool(b) => Some(V::from(*b as i64)),
+            _ => None,
+        },
+        "float" => match v {
+            V::Number(n) => n.as_f64().map(V::from),
+            V::String(s) => s.trim().parse::<f64>().ok().map(V::from),
+            V::Bool(b) => Some(V::from(if *b { 1.0 } else { 0.0 })),
+            _ => None,
+        },
+        "bool" => match v {
+            V::Bool(b) => Some(V::from(*b)),
+            V::Number(n) => n.as_i64().map(|i| V::from(i != 0)),
+            V::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(V::from(true)),
+                "false" | "0" => Some(V::from(false)),
+                _ => None,
+            },
+            _ => None,
+        },
+        "string" => match v {
+            V::String(s) => Some(V::from(s.clone())),
+            V::Number(n) => Some(V::from(n.to_string())),
+            V::Bool(b) => Some(V::from(b.to_string())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Normalize a raw `config.json` into the shape the `LlamaConfig`/`PhiConfig`
+/// deserializers expect: lift a nested `text_config` block, resolve each field
+/// through its aliases, coerce it to the declared type and apply defaults.
+/// Every field that fails to coerce is collected and reported together rather
+/// than aborting on the first serde error.
+fn coerce_config(bytes: &[u8]) -> Result<Vec<u8>> {
+    use serde_json::Value;
+
+    let root: Value = serde_json::from_slice(bytes)?;
+    let mut obj = match root {
+        Value::Object(o) => o,
+        other => bail!("config.json is not an object: {other}"),
+    };
+
+    // Multimodal HF configs nest the language-model fields under `text_config`;
+    // lift them to the top level without clobbering any explicit top-level key.
+    if let Some(Value::Object(text)) = obj.get("text_config").cloned() {
+        for (k, v) in text {
+            obj.entry(k).or_insert(v);
+        }
+    }
+
+    let mut errors = Vec::new();
+    for spec in FIELD_SPECS {
+        let raw = std::iter::once(spec.name)
+            .chain(spec.aliases.iter().copied())
+            .find_map(|k| obj.get(k).cloned());
+
+        match raw {
+            Some(v) => match coerce_value(spec.ty, &v) {
+                Some(c) => {
+                    obj.insert(spec.name.to_string(), c);
+                }
+                None => errors.push(format!("{}: cannot coerce {} to {}", spec.name, v, spec.ty)),
+            },
+            None => {
+                if let Some(default) = spec.default {
+                    obj.insert(spec.name.to_string(), serde_json::from_str(default).unwrap());
+                }
+            }
+        }
+    }
+
+    // `num_key_value_heads` defaults to the attention head count (MHA).
+    if !obj.contains_key("num_key_value_heads") {
+        if let Some(h) = obj.get("num_attention_heads").cloned() {
+            obj.insert("num_key_value_heads".to_string(), h);
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("failed to coerce config.json fields:\n{}", errors.join("\n"));
+    }
+
+    Ok(serde_json::to_vec(&Value::Object(obj))?)
+}
+
+/// Build a `config.json`-shaped JSON blob from a GGUF file's metadata so the
+/// existing `LlamaConfig`/`PhiConfig` deserializers can consume it unchanged.
+/// Returns `None` when the repo ships no GGUF weights.
+fn gguf_config_json(repo: &Repo) -> Result<Option<Vec<u8>>> {
+    let Some(path) = gguf_filename(repo) else {
+        return Ok(None);
+    };
+
+    let fp = std::fs::File::open(&path)?;
+    let content = unsafe { memmap2::MmapOptions::new().map(&fp)? };
+    let header = gguf::parse(&content)?;
+    let md = &header.metadata;
+
+    let arch = md
+        .get("general.architecture")
+        .and_then(gguf::Value::as_str)
+        .unwrap_or("llama");
+
+    let geti = |key: &str| md.get(key).and_then(gguf::Value::as_i64);
+    let getf = |key: &str| md.get(key).and_then(gguf::Value::as_f64);
+
+    let head_count = geti(&format!("{arch}.attention.head_count"));
+    let mut obj = serde_json::Map::new();
+    let put_i = |obj: &mut serde_json::Map<String, serde_json::Value>, k: &str, v: Option<i64>| {
+        if let Some(v) = v {
+            obj.insert(k.to_string(), serde_json::json!(v));
+        }
+    };
+    let put_f = |obj: &mut serde_json::Map<String, serde_json::Value>, k: &str, v: Option<f64>| {
+        if let Some(v) = v {
+            obj.insert(k.to_string(), serde_json::json!(v));
+        }
+    };
+
+    obj.insert("model_type".to_string(), serde_json::json!(arch));
+    put_i(&mut obj, "hidden_size", geti(&format!("{arch}.embedding_length")));
+    put_i(&mut obj, "intermediate_size", geti(&format!("{arch}.feed_forward_length")));
+    put_i(&mut obj, "num_hidden_layers", geti(&format!("{arch}.block_count")));
+    put_i(&mut obj, "num_attention_heads", head_count);
+    put_i(
+        &mut obj,
+        "num_key_value_heads",
+        geti(&format!("{arch}.attention.head_count_kv")).or(head_count),
+    );
+    put_i(&mut obj, "vocab_size", geti(&format!("{arch}.vocab_size")));
+    put_i(
+        &mut obj,
+        "max_position_embeddings",
+        geti(&format!("{arch}.context_length")),
+    );
+    put_f(
+        &mut obj,
+        "rms_norm_eps",
+        getf(&format!("{arch}.attention.layer_norm_rms_epsilon")),
+    );
+    put_f(&mut obj, "rope_theta", getf(&format!("{arch}.rope.freq_base")));
+
+    Ok(Some(serde_json::to_vec(&serde_json::Value::Object(obj))?))
+}
+
 fn load_one_config<T>(
     err: &mut String,
     args: &LoaderArgs,